@@ -0,0 +1,51 @@
+use cgmath::Vector3;
+
+use crate::Ray;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub min: Vector3<f64>,
+    pub max: Vector3<f64>,
+}
+
+impl Aabb {
+    pub fn new(min: Vector3<f64>, max: Vector3<f64>) -> Self {
+        Aabb { min, max }
+    }
+
+    pub fn hit(&self, ray: &Ray, mut t_min: f64, mut t_max: f64) -> bool {
+        for axis in 0..3 {
+            let inv_d = 1.0 / ray.direction[axis];
+            let mut t0 = (self.min[axis] - ray.origin[axis]) * inv_d;
+            let mut t1 = (self.max[axis] - ray.origin[axis]) * inv_d;
+
+            if inv_d < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+
+            if t_max <= t_min {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn surrounding_box(a: &Aabb, b: &Aabb) -> Aabb {
+        let min = Vector3 {
+            x: a.min.x.min(b.min.x),
+            y: a.min.y.min(b.min.y),
+            z: a.min.z.min(b.min.z),
+        };
+        let max = Vector3 {
+            x: a.max.x.max(b.max.x),
+            y: a.max.y.max(b.max.y),
+            z: a.max.z.max(b.max.z),
+        };
+
+        Aabb::new(min, max)
+    }
+}
@@ -0,0 +1,62 @@
+use rand::{rngs::SmallRng, Rng};
+
+use crate::{aabb::Aabb, hittable::{HitRecord, Hittable}, Ray};
+
+pub struct BvhNode {
+    left: Box<dyn Hittable + Send + Sync>,
+    right: Box<dyn Hittable + Send + Sync>,
+    bbox: Aabb,
+}
+
+impl BvhNode {
+    pub fn build(mut objects: Vec<Box<dyn Hittable + Send + Sync>>, rng: &mut SmallRng) -> Box<dyn Hittable + Send + Sync> {
+        let axis = rng.gen_range(0..3);
+        objects.sort_by(|a, b| {
+            let box_a = a.bounding_box().expect("object in BvhNode has no bounding box");
+            let box_b = b.bounding_box().expect("object in BvhNode has no bounding box");
+            box_a.min[axis].partial_cmp(&box_b.min[axis]).unwrap()
+        });
+
+        match objects.len() {
+            0 => panic!("BvhNode::build called with no objects"),
+            1 => objects.pop().unwrap(),
+            2 => {
+                let right = objects.pop().unwrap();
+                let left = objects.pop().unwrap();
+                let bbox = Aabb::surrounding_box(
+                    &left.bounding_box().expect("object in BvhNode has no bounding box"),
+                    &right.bounding_box().expect("object in BvhNode has no bounding box"),
+                );
+                Box::new(BvhNode { left, right, bbox })
+            }
+            len => {
+                let right_objects = objects.split_off(len / 2);
+                let left = BvhNode::build(objects, rng);
+                let right = BvhNode::build(right_objects, rng);
+                let bbox = Aabb::surrounding_box(
+                    &left.bounding_box().expect("object in BvhNode has no bounding box"),
+                    &right.bounding_box().expect("object in BvhNode has no bounding box"),
+                );
+                Box::new(BvhNode { left, right, bbox })
+            }
+        }
+    }
+}
+
+impl Hittable for BvhNode {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        if !self.bbox.hit(ray, t_min, t_max) {
+            return None;
+        }
+
+        let hit_left = self.left.hit(ray, t_min, t_max);
+        let t_max = hit_left.as_ref().map_or(t_max, |record| record.t);
+        let hit_right = self.right.hit(ray, t_min, t_max);
+
+        hit_right.or(hit_left)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bbox)
+    }
+}
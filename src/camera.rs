@@ -10,6 +10,8 @@ pub struct Camera {
     pub field_of_view: f64,
     pub focal_length: f64,
     pub aperture: f64,
+    pub time0: f64,
+    pub time1: f64,
 }
 
 impl Camera {
@@ -30,6 +32,7 @@ pub struct CameraRayGen<'a> {
     pub camera: &'a Camera,
     pub rng: SmallRng,
     unit_distribution: Uniform<f64>,
+    time_distribution: Uniform<f64>,
     x_direction: Vector3<f64>,
     y_direction: Vector3<f64>,
     x_aperture: Vector3<f64>,
@@ -46,6 +49,7 @@ impl<'a> CameraRayGen<'a> {
         let upper_left = screen_center - x_direction * 0.5 - y_direction * 0.5;
         let rng = SmallRng::from_entropy();
         let unit_distribution = Uniform::new(0.0, 1.0);
+        let time_distribution = Uniform::new(camera.time0, camera.time1);
         let x_aperture = x_direction.normalize_to(camera.aperture * 0.5);
         let y_aperture = y_direction.normalize_to(camera.aperture * 0.5);
         // println!("center_direction: {center_direction:?}");
@@ -58,6 +62,7 @@ impl<'a> CameraRayGen<'a> {
             camera,
             rng,
             unit_distribution,
+            time_distribution,
             x_direction,
             y_direction,
             x_aperture,
@@ -87,6 +92,8 @@ impl<'a> CameraRayGen<'a> {
                  + self.x_aperture * self.rng.sample(self.unit_distribution)
                  + self.y_aperture * self.rng.sample(self.unit_distribution);
 
-        Ray::new(base, point - base)
+        let time = self.rng.sample(self.time_distribution);
+
+        Ray::new(base, point - base, time)
     }
 }
\ No newline at end of file
@@ -1,17 +1,21 @@
 use cgmath::Vector3;
 
+use crate::aabb::Aabb;
+
 pub trait Convex {
     fn support(&self, direction: Vector3<f64>) -> f64;
-}
 
-pub trait ConvexHull {
-    fn hull_support(&self, direction: Vector3<f64>) -> f64;
-}
+    fn bounding_box(&self) -> Aabb {
+        let max_x = self.support(Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+        let min_x = -self.support(Vector3 { x: -1.0, y: 0.0, z: 0.0 });
+        let max_y = self.support(Vector3 { x: 0.0, y: 1.0, z: 0.0 });
+        let min_y = -self.support(Vector3 { x: 0.0, y: -1.0, z: 0.0 });
+        let max_z = self.support(Vector3 { x: 0.0, y: 0.0, z: 1.0 });
+        let min_z = -self.support(Vector3 { x: 0.0, y: 0.0, z: -1.0 });
 
-impl<T> ConvexHull for T
-where T: Convex
-{
-    fn hull_support(&self, direction: Vector3<f64>) -> f64 {
-        self.support(direction)
+        Aabb::new(
+            Vector3 { x: min_x, y: min_y, z: min_z },
+            Vector3 { x: max_x, y: max_y, z: max_z },
+        )
     }
 }
\ No newline at end of file
@@ -1,24 +1,39 @@
 use cgmath::{Vector3, InnerSpace};
 
-use crate::{hittable::Hittable, Ray};
+use crate::{aabb::Aabb, hittable::{HitRecord, Hittable}, material::Material, Ray};
 
 
 
 pub struct HalfPlane {
     pub normal: Vector3<f64>,
     pub offset: f64,
+    pub material: Box<dyn Material + Send + Sync>,
 }
 
 impl Hittable for HalfPlane {
-    fn hit(&self, ray: &crate::Ray) -> Option<(f64, crate::Ray)> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
         let proj = ray.origin.project_on(self.normal);
         let t = (self.offset - proj.magnitude()) / (ray.direction.dot(self.normal));
-        let intersect = ray.at(t);
 
-        if t < 0.0 {
+        if t <= t_min || t >= t_max {
             return None
         }
 
-        Some((t, Ray::new(intersect, self.normal)))
+        let point = ray.at(t);
+        let front_face = ray.direction.dot(self.normal) < 0.0;
+        let normal = if front_face { self.normal } else { -self.normal };
+
+        Some(HitRecord {
+            t,
+            point,
+            normal,
+            front_face,
+            material: self.material.as_ref(),
+            light: self.as_light(),
+        })
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
     }
 }
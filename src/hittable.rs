@@ -1,5 +1,29 @@
-use crate::Ray;
+use cgmath::Vector3;
+
+use crate::{aabb::Aabb, light::SphereLight, material::Material, Ray};
+
+pub struct HitRecord<'a> {
+    pub t: f64,
+    pub point: Vector3<f64>,
+    pub normal: Vector3<f64>,
+    pub front_face: bool,
+    pub material: &'a (dyn Material + Send + Sync),
+    /// This hit's object as an area light, if any — set from `as_light` so
+    /// light-sampling code can identify the light actually hit instead of
+    /// re-deriving it from `point`'s distance to each `World::lights` entry.
+    pub light: Option<SphereLight>,
+}
 
 pub trait Hittable {
-    fn hit(&self, ray: &Ray) -> Option<(f64, Ray)>;
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>>;
+    fn bounding_box(&self) -> Option<Aabb>;
+
+    /// This object's geometry as an area light, if its material emits
+    /// non-black radiance. `World::build` uses this to derive `World::lights`
+    /// straight from the scene instead of describing each light a second time.
+    /// Only `Sphere` overrides this; the default returns `None`, so other
+    /// emissive objects still glow but are not light-sampled.
+    fn as_light(&self) -> Option<SphereLight> {
+        None
+    }
 }
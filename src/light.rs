@@ -0,0 +1,82 @@
+use std::f64::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+use image::Rgb;
+use rand::{distributions::Uniform, rngs::SmallRng, Rng};
+
+pub struct LightSample {
+    pub direction: Vector3<f64>,
+    pub radiance: Rgb<f64>,
+    pub pdf: f64,
+}
+
+#[derive(Clone, Copy)]
+pub struct SphereLight {
+    pub center: Vector3<f64>,
+    pub radius: f64,
+    pub radiance: Rgb<f64>,
+}
+
+impl SphereLight {
+    /// The exact distance from `origin` to this light's surface along
+    /// `direction`, used as a shadow ray's `t_max` instead of the
+    /// center-line distance (which only holds for a ray aimed at the
+    /// light's center).
+    pub fn hit_distance(&self, origin: Vector3<f64>, direction: Vector3<f64>) -> f64 {
+        let pc = self.center - origin;
+        let pc2 = pc.dot(direction);
+        let descriminant = (self.radius * self.radius - pc.magnitude2() + pc2 * pc2).max(0.0);
+
+        pc2 - descriminant.sqrt()
+    }
+
+    fn cos_theta_max(&self, origin: Vector3<f64>) -> Option<f64> {
+        let dist2 = (self.center - origin).magnitude2();
+        if dist2 <= self.radius * self.radius {
+            return None
+        }
+
+        Some((1.0 - self.radius * self.radius / dist2).sqrt())
+    }
+
+    pub fn pdf(&self, origin: Vector3<f64>) -> f64 {
+        match self.cos_theta_max(origin) {
+            Some(cos_theta_max) => 1.0 / (2.0 * PI * (1.0 - cos_theta_max)),
+            None => 0.0,
+        }
+    }
+
+    pub fn sample(&self, origin: Vector3<f64>, rng: &mut SmallRng) -> LightSample {
+        let w = (self.center - origin).normalize();
+
+        let cos_theta_max = match self.cos_theta_max(origin) {
+            Some(cos_theta_max) => cos_theta_max,
+            None => return LightSample { direction: w, radiance: self.radiance, pdf: 0.0 },
+        };
+
+        let unit = Uniform::new(0.0, 1.0);
+        let u1: f64 = rng.sample(unit);
+        let u2: f64 = rng.sample(unit);
+
+        let cos_theta = 1.0 - u1 * (1.0 - cos_theta_max);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+        let phi = 2.0 * PI * u2;
+
+        let a = if w.x.abs() > 0.9 {
+            Vector3 { x: 0.0, y: 1.0, z: 0.0 }
+        } else {
+            Vector3 { x: 1.0, y: 0.0, z: 0.0 }
+        };
+        let v = w.cross(a).normalize();
+        let u = w.cross(v);
+
+        let direction = u * (sin_theta * phi.cos()) + v * (sin_theta * phi.sin()) + w * cos_theta;
+        let pdf = 1.0 / (2.0 * PI * (1.0 - cos_theta_max));
+
+        LightSample {
+            direction: direction.normalize(),
+            radiance: self.radiance,
+            pdf,
+        }
+    }
+}
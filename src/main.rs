@@ -1,153 +1,248 @@
 use std::path::Path;
 
+use bvh::BvhNode;
 use camera::{Camera, CameraRayGen};
 use half_plane::HalfPlane;
 use hittable::Hittable;
 use image::{RgbImage, Rgb};
 use cgmath::{Vector3, InnerSpace};
+use light::SphereLight;
+use material::{Dielectric, DiffuseLight, Lambertian, Metal};
+use moving_sphere::MovingSphere;
 use sphere::Sphere;
-use rand::{Rng, thread_rng, distributions::Uniform, RngCore, rngs::SmallRng};
+use rand::{Rng, rngs::SmallRng, SeedableRng};
 use rayon::prelude::*;
 
+pub mod aabb;
+pub mod bvh;
 pub mod hittable;
 pub mod sphere;
 pub mod camera;
 pub mod convex;
 pub mod half_plane;
+pub mod light;
+pub mod material;
+pub mod moving_sphere;
 
 const IMG_WIDTH: u32 = 800;
 const IMG_HEIGHT: u32 = 450;
 const IMG_SAMPLES: u32 = 100;
 
 pub struct World {
-    pub stuff: Vec<Box<dyn Hittable + Send + Sync>>
+    pub stuff: Vec<Box<dyn Hittable + Send + Sync>>,
+    pub lights: Vec<SphereLight>,
+}
+
+impl World {
+    /// Objects with no `bounding_box` (e.g. an infinite `HalfPlane`) can't live
+    /// in the BVH, so they're split off and tested linearly alongside it.
+    /// `World::lights` is derived from `as_light` rather than taken as a
+    /// parameter, so a light's geometry is only ever described once.
+    pub fn build(objects: Vec<Box<dyn Hittable + Send + Sync>>, rng: &mut SmallRng) -> Self {
+        let lights = objects.iter().filter_map(|object| object.as_light()).collect();
+
+        let (bounded, mut unbounded): (Vec<_>, Vec<_>) = objects.into_iter()
+            .partition(|object| object.bounding_box().is_some());
+
+        let mut stuff = Vec::new();
+        if !bounded.is_empty() {
+            stuff.push(BvhNode::build(bounded, rng));
+        }
+        stuff.append(&mut unbounded);
+
+        World { stuff, lights }
+    }
 }
 
 pub struct Ray {
     pub origin: Vector3<f64>,
     pub direction: Vector3<f64>,
+    pub time: f64,
 }
 
 impl Ray {
-    pub fn new(origin: Vector3<f64>, direction: Vector3<f64>) -> Self {
+    pub fn new(origin: Vector3<f64>, direction: Vector3<f64>, time: f64) -> Self {
         Ray {
             origin,
-            direction: direction.normalize()
+            direction: direction.normalize(),
+            time,
         }
     }
     
     pub fn at(&self, t: f64) -> Vector3<f64> {
         self.origin + t * self.direction
     }
+}
 
-    pub fn rand_diffuse(&self, rng: &mut SmallRng) -> Self {
-        let dist = Uniform::new_inclusive(-1.0, 1.0);
-        let v = loop {
-            let x = rng.sample(dist);
-            let y = rng.sample(dist);
-            let z = rng.sample(dist);
-
-            let v = Vector3 { x, y, z };
-            if v.magnitude2() <= 1.0 {
-                // break v
-                break v.normalize()
-            }
-        };
+fn power_heuristic(pdf_a: f64, pdf_b: f64) -> f64 {
+    let a2 = pdf_a * pdf_a;
+    let b2 = pdf_b * pdf_b;
 
-        Ray {
-            origin: self.origin,
-            direction: self.direction + v
-        }
+    if a2 + b2 == 0.0 {
+        0.0
+    } else {
+        a2 / (a2 + b2)
+    }
+}
+
+/// The solid-angle pdf of sampling `record`'s hit point from `origin` via
+/// next-event estimation, if `record` is a hit on an object that `as_light`
+/// reports as a light (`None` for any other hit, emissive or not).
+fn light_sampling_pdf(world: &World, origin: Vector3<f64>, record: &hittable::HitRecord) -> Option<f64> {
+    Some(record.light?.pdf(origin) / world.lights.len() as f64)
+}
+
+fn sample_direct_lighting(world: &World, record: &hittable::HitRecord, ray_time: f64, rng: &mut SmallRng) -> Rgb<f64> {
+    if world.lights.is_empty() {
+        return Rgb([0.0, 0.0, 0.0])
     }
 
-    pub fn sunward_ray(&self) -> Option<Self> {
-        let sun_dir = - Vector3 { x: 1.0, y: 5.0, z: 0.5 };
-        if sun_dir.dot(self.direction) < 0.0 {
-            return None
-        }
+    let light = &world.lights[rng.gen_range(0..world.lights.len())];
+    let sample = light.sample(record.point, rng);
+    let light_pdf = sample.pdf / world.lights.len() as f64;
 
-        Some(Ray {
-            origin: self.origin,
-            direction: sun_dir
-        })
+    let cos_theta = record.normal.dot(sample.direction).max(0.0);
+    if light_pdf <= 0.0 || cos_theta <= 0.0 {
+        return Rgb([0.0, 0.0, 0.0])
     }
 
-    pub fn rand_ray(&self, prob: f64, rng: &mut SmallRng) -> Self {
-        if rng.gen_bool(prob) {
-            self.sunward_ray().unwrap_or(self.rand_diffuse(rng))
-        } else {
-            self.rand_diffuse(rng)
-        }
+    let shadow_ray = Ray::new(record.point, sample.direction, ray_time);
+    let light_distance = light.hit_distance(record.point, shadow_ray.direction);
+
+    let occluded = world.stuff.iter().any(|item| item.hit(&shadow_ray, 0.001, light_distance - 0.001).is_some());
+    if occluded {
+        return Rgb([0.0, 0.0, 0.0])
     }
+
+    let bsdf_pdf = record.material.scattering_pdf(record, sample.direction);
+    let weight = power_heuristic(light_pdf, bsdf_pdf);
+    let f = record.material.brdf(record, sample.direction);
+
+    Rgb([
+        f.0[0] * sample.radiance.0[0] * cos_theta * weight / light_pdf,
+        f.0[1] * sample.radiance.0[1] * cos_theta * weight / light_pdf,
+        f.0[2] * sample.radiance.0[2] * cos_theta * weight / light_pdf,
+    ])
 }
 
-fn trace_path(ray: Ray, world: &World, depth: usize, rng: &mut SmallRng) -> Rgb<f64> {
+/// `specular_bounce` is true for the primary camera ray and for rays reflected
+/// or refracted by a specular material, where light sampling never ran on the
+/// previous hit so any emission hit here must count in full rather than being
+/// weighted against a light-sampling pdf. `bsdf_pdf` is the scattering pdf the
+/// previous hit sampled `ray`'s direction with, used to MIS-weight that emission.
+fn trace_path(ray: Ray, world: &World, depth: usize, specular_bounce: bool, bsdf_pdf: f64, rng: &mut SmallRng) -> Rgb<f64> {
     if depth == 0 {
         return Rgb([0.0, 0.0, 0.0])
     }
+
+    let mut closest_so_far = f64::INFINITY;
     let mut hit = None;
     for item in &world.stuff {
-        if let Some((t, norm)) = item.hit(&ray) {
-            hit = match hit {
-                Some((t_new, norm_new)) => if t_new < t {
-                    Some((t_new, norm_new))
-                } else {
-                    Some((t, norm))
-                },
-                None => Some((t, norm)),
-            };
+        if let Some(record) = item.hit(&ray, 0.001, closest_so_far) {
+            closest_so_far = record.t;
+            hit = Some(record);
         }
     }
 
-    if let Some((_, norm)) = hit {
-        // let p = 10.0 - depth as f64;
-        // let p = p * p / 100.0;
-        // let p = p.clamp(0.0, 1.0);
-        let new_ray = norm.rand_diffuse(rng);
-        let next = trace_path(new_ray, world, depth - 1, rng);
-        return Rgb([next.0[0] * 0.4, next.0[1] * 0.4, next.0[2] * 0.4])
-    }
-    
-    let unit = ray.direction.normalize();
-    let t = 0.5 * (unit.y + 1.0);
-    let ti = 1.0 - t;
+    let record = match hit {
+        Some(record) => record,
+        None => {
+            let unit = ray.direction.normalize();
+            let t = 0.5 * (unit.y + 1.0);
+            let ti = 1.0 - t;
+            return Rgb([ti + t * 0.5, ti + t * 0.7, 1.0])
+        }
+    };
 
-    Rgb([
-        ti + t * 0.5,
-        ti + t * 0.7,
+    let emitted = record.material.emitted();
+    let emitted_weight = if specular_bounce {
         1.0
-    ])
+    } else {
+        match light_sampling_pdf(world, ray.origin, &record) {
+            Some(light_pdf) => power_heuristic(bsdf_pdf, light_pdf),
+            None => 1.0,
+        }
+    };
+
+    let mut color = Rgb([
+        emitted.0[0] * emitted_weight,
+        emitted.0[1] * emitted_weight,
+        emitted.0[2] * emitted_weight,
+    ]);
+
+    if !record.material.is_specular() {
+        let direct = sample_direct_lighting(world, &record, ray.time, rng);
+        color.0[0] += direct.0[0];
+        color.0[1] += direct.0[1];
+        color.0[2] += direct.0[2];
+    }
+
+    if let Some((scattered, attenuation)) = record.material.scatter(&ray, &record, rng) {
+        let pdf = record.material.scattering_pdf(&record, scattered.direction);
+        let next = trace_path(scattered, world, depth - 1, record.material.is_specular(), pdf, rng);
+        color.0[0] += next.0[0] * attenuation.0[0];
+        color.0[1] += next.0[1] * attenuation.0[1];
+        color.0[2] += next.0[2] * attenuation.0[2];
+    }
+
+    color
 }
 
 fn main() {
 
-    let world = World {
-        stuff: vec![
+    let mut build_rng = SmallRng::from_entropy();
+    let world = World::build(
+        vec![
             Box::new(Sphere {
                 center: Vector3 { x: 0.0, y: 0.0, z: -1.0 },
                 radius: 0.5,
+                material: Box::new(Lambertian { albedo: Rgb([0.6, 0.3, 0.3]) }),
             }),
             Box::new(Sphere {
                 center: Vector3 { x: 1.5, y: 0.0, z: -3.0 },
                 radius: 0.5,
+                material: Box::new(Metal { albedo: Rgb([0.8, 0.8, 0.8]), fuzz: 0.1 }),
+            }),
+            Box::new(Sphere {
+                center: Vector3 { x: -1.0, y: 0.0, z: -1.5 },
+                radius: 0.5,
+                material: Box::new(Dielectric { ior: 1.5 }),
             }),
             Box::new(HalfPlane {
                 normal: Vector3 { x: 0.0, y: 1.0, z: 0.0 },
                 offset: -0.5,
+                material: Box::new(Lambertian { albedo: Rgb([0.5, 0.5, 0.5]) }),
             }),
-        ]
-    };
+            Box::new(MovingSphere {
+                center0: Vector3 { x: 0.6, y: -0.3, z: -0.8 },
+                center1: Vector3 { x: 0.6, y: 0.1, z: -0.8 },
+                time0: 0.0,
+                time1: 1.0,
+                radius: 0.2,
+                material: Box::new(Lambertian { albedo: Rgb([0.3, 0.3, 0.7]) }),
+            }),
+            Box::new(Sphere {
+                center: Vector3 { x: -0.3, y: 1.2, z: -1.0 },
+                radius: 0.4,
+                material: Box::new(DiffuseLight { emit: Rgb([4.0, 4.0, 4.0]) }),
+            }),
+        ],
+        &mut build_rng,
+    );
 
     let camera = Camera {
-        ray: Ray { 
+        ray: Ray {
             origin: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
             direction: Vector3 { x: 0.0, y: 0.0, z: -1.0 },
+            time: 0.0,
         },
         image_width: IMG_WIDTH,
         image_height: IMG_HEIGHT,
         focal_length: 0.87,
         aperture: 0.03,
         field_of_view: 2.0,
+        time0: 0.0,
+        time1: 1.0,
     };
 
     let pixels: Vec<_> = (0..IMG_HEIGHT).into_par_iter().map(|y| {
@@ -160,7 +255,7 @@ fn main() {
 
             for _ in 0..IMG_SAMPLES {
                 let ray = camera_ray_gen.gen_ray(x, y);
-                let color = trace_path(ray, &world, 20, &mut camera_ray_gen.rng);
+                let color = trace_path(ray, &world, 20, true, 0.0, &mut camera_ray_gen.rng);
 
                 r += color.0[0];
                 g += color.0[1];
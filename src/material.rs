@@ -0,0 +1,160 @@
+use std::f64::consts::PI;
+
+use cgmath::{InnerSpace, Vector3};
+use image::Rgb;
+use rand::{distributions::Uniform, rngs::SmallRng, Rng};
+
+use crate::{hittable::HitRecord, Ray};
+
+pub trait Material {
+    fn scatter(&self, incoming: &Ray, hit: &HitRecord, rng: &mut SmallRng) -> Option<(Ray, Rgb<f64>)>;
+
+    fn emitted(&self) -> Rgb<f64> {
+        Rgb([0.0, 0.0, 0.0])
+    }
+
+    /// Whether `scatter` samples a single delta direction (mirror reflection,
+    /// refraction) rather than a continuous lobe. Specular materials have no
+    /// well-defined BRDF value or pdf, so they are skipped by light sampling.
+    fn is_specular(&self) -> bool {
+        false
+    }
+
+    fn brdf(&self, _hit: &HitRecord, _direction: Vector3<f64>) -> Rgb<f64> {
+        Rgb([0.0, 0.0, 0.0])
+    }
+
+    fn scattering_pdf(&self, _hit: &HitRecord, _direction: Vector3<f64>) -> f64 {
+        0.0
+    }
+}
+
+fn random_in_unit_sphere(rng: &mut SmallRng) -> Vector3<f64> {
+    let dist = Uniform::new_inclusive(-1.0, 1.0);
+    loop {
+        let v = Vector3 {
+            x: rng.sample(dist),
+            y: rng.sample(dist),
+            z: rng.sample(dist),
+        };
+        if v.magnitude2() <= 1.0 {
+            return v;
+        }
+    }
+}
+
+fn random_unit_vector(rng: &mut SmallRng) -> Vector3<f64> {
+    random_in_unit_sphere(rng).normalize()
+}
+
+/// True if `v` is close enough to zero in all dimensions that normalizing it
+/// would be numerically unstable.
+fn near_zero(v: Vector3<f64>) -> bool {
+    const EPSILON: f64 = 1e-8;
+    v.x.abs() < EPSILON && v.y.abs() < EPSILON && v.z.abs() < EPSILON
+}
+
+fn reflect(v: Vector3<f64>, n: Vector3<f64>) -> Vector3<f64> {
+    v - 2.0 * v.dot(n) * n
+}
+
+fn refract(unit_dir: Vector3<f64>, normal: Vector3<f64>, eta_ratio: f64, cos_theta: f64) -> Vector3<f64> {
+    let r_perp = eta_ratio * (unit_dir + cos_theta * normal);
+    let r_par = -((1.0 - r_perp.magnitude2()).abs().sqrt()) * normal;
+    r_perp + r_par
+}
+
+fn schlick_reflectance(cos_theta: f64, eta_ratio: f64) -> f64 {
+    let r0 = (1.0 - eta_ratio) / (1.0 + eta_ratio);
+    let r0 = r0 * r0;
+    r0 + (1.0 - r0) * (1.0 - cos_theta).powi(5)
+}
+
+#[derive(Clone, Copy)]
+pub struct Lambertian {
+    pub albedo: Rgb<f64>,
+}
+
+impl Material for Lambertian {
+    fn scatter(&self, incoming: &Ray, hit: &HitRecord, rng: &mut SmallRng) -> Option<(Ray, Rgb<f64>)> {
+        let mut direction = hit.normal + random_unit_vector(rng);
+        if near_zero(direction) {
+            direction = hit.normal;
+        }
+        Some((Ray::new(hit.point, direction, incoming.time), self.albedo))
+    }
+
+    fn brdf(&self, _hit: &HitRecord, _direction: Vector3<f64>) -> Rgb<f64> {
+        Rgb([self.albedo.0[0] / PI, self.albedo.0[1] / PI, self.albedo.0[2] / PI])
+    }
+
+    fn scattering_pdf(&self, hit: &HitRecord, direction: Vector3<f64>) -> f64 {
+        hit.normal.dot(direction.normalize()).max(0.0) / PI
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Metal {
+    pub albedo: Rgb<f64>,
+    pub fuzz: f64,
+}
+
+impl Material for Metal {
+    fn scatter(&self, incoming: &Ray, hit: &HitRecord, rng: &mut SmallRng) -> Option<(Ray, Rgb<f64>)> {
+        let reflected = reflect(incoming.direction, hit.normal) + self.fuzz * random_in_unit_sphere(rng);
+
+        if reflected.dot(hit.normal) <= 0.0 {
+            return None;
+        }
+
+        Some((Ray::new(hit.point, reflected, incoming.time), self.albedo))
+    }
+
+    fn is_specular(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Dielectric {
+    pub ior: f64,
+}
+
+impl Material for Dielectric {
+    fn scatter(&self, incoming: &Ray, hit: &HitRecord, rng: &mut SmallRng) -> Option<(Ray, Rgb<f64>)> {
+        let eta_ratio = if hit.front_face { 1.0 / self.ior } else { self.ior };
+        let normal = hit.normal;
+
+        let unit_dir = incoming.direction.normalize();
+        let cos_theta = (-unit_dir).dot(normal).min(1.0);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+
+        let cannot_refract = eta_ratio * sin_theta > 1.0;
+        let direction = if cannot_refract || schlick_reflectance(cos_theta, eta_ratio) > rng.sample(Uniform::new(0.0, 1.0)) {
+            reflect(unit_dir, normal)
+        } else {
+            refract(unit_dir, normal, eta_ratio, cos_theta)
+        };
+
+        Some((Ray::new(hit.point, direction, incoming.time), Rgb([1.0, 1.0, 1.0])))
+    }
+
+    fn is_specular(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DiffuseLight {
+    pub emit: Rgb<f64>,
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _incoming: &Ray, _hit: &HitRecord, _rng: &mut SmallRng) -> Option<(Ray, Rgb<f64>)> {
+        None
+    }
+
+    fn emitted(&self) -> Rgb<f64> {
+        self.emit
+    }
+}
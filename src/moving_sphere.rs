@@ -0,0 +1,65 @@
+use cgmath::{InnerSpace, Vector3};
+
+use crate::{aabb::Aabb, hittable::{HitRecord, Hittable}, material::Material, Ray};
+
+pub struct MovingSphere {
+    pub center0: Vector3<f64>,
+    pub center1: Vector3<f64>,
+    pub time0: f64,
+    pub time1: f64,
+    pub radius: f64,
+    pub material: Box<dyn Material + Send + Sync>,
+}
+
+impl MovingSphere {
+    fn center_at(&self, time: f64) -> Vector3<f64> {
+        let t = (time - self.time0) / (self.time1 - self.time0);
+        self.center0 + t * (self.center1 - self.center0)
+    }
+}
+
+impl Hittable for MovingSphere {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let center = self.center_at(ray.time);
+        let pc = center - ray.origin;
+        let pc2 = pc.dot(ray.direction);
+
+        let descriminant = self.radius * self.radius - pc.magnitude2() + pc2 * pc2;
+
+        if descriminant < 0.0 {
+            return None
+        }
+
+        let sqrt_d = descriminant.sqrt();
+
+        let mut root = pc2 - sqrt_d;
+        if root <= t_min || root >= t_max {
+            root = pc2 + sqrt_d;
+            if root <= t_min || root >= t_max {
+                return None
+            }
+        }
+
+        let point = ray.at(root);
+        let outward_normal = (point - center) / self.radius;
+        let front_face = ray.direction.dot(outward_normal) < 0.0;
+        let normal = if front_face { outward_normal } else { -outward_normal };
+
+        Some(HitRecord {
+            t: root,
+            point,
+            normal,
+            front_face,
+            material: self.material.as_ref(),
+            light: self.as_light(),
+        })
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vector3 { x: self.radius, y: self.radius, z: self.radius };
+        let box0 = Aabb::new(self.center0 - radius, self.center0 + radius);
+        let box1 = Aabb::new(self.center1 - radius, self.center1 + radius);
+
+        Some(Aabb::surrounding_box(&box0, &box1))
+    }
+}
@@ -1,15 +1,15 @@
 use cgmath::{Vector3, InnerSpace};
 
-use crate::{Ray, hittable::Hittable, convex::Convex};
+use crate::{aabb::Aabb, hittable::{HitRecord, Hittable}, convex::Convex, light::SphereLight, material::Material, Ray};
 
-#[derive(Clone)]
 pub struct Sphere {
     pub center: Vector3<f64>,
     pub radius: f64,
+    pub material: Box<dyn Material + Send + Sync>,
 }
 
 impl Hittable for Sphere {
-    fn hit(&self, ray: &Ray) -> Option<(f64, Ray)> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
         let pc = self.center - ray.origin;
         let pc2 = pc.dot(ray.direction);
 
@@ -19,16 +19,42 @@ impl Hittable for Sphere {
             return None
         }
 
-        let t = pc2 - descriminant;
+        let sqrt_d = descriminant.sqrt();
 
-        if t < 0.0 {
-            return None
+        let mut root = pc2 - sqrt_d;
+        if root <= t_min || root >= t_max {
+            root = pc2 + sqrt_d;
+            if root <= t_min || root >= t_max {
+                return None
+            }
         }
 
-        let intersection = ray.at(t);
-        let normal = Ray::new(intersection, intersection - self.center);
+        let point = ray.at(root);
+        let outward_normal = (point - self.center) / self.radius;
+        let front_face = ray.direction.dot(outward_normal) < 0.0;
+        let normal = if front_face { outward_normal } else { -outward_normal };
+
+        Some(HitRecord {
+            t: root,
+            point,
+            normal,
+            front_face,
+            material: self.material.as_ref(),
+            light: self.as_light(),
+        })
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Convex::bounding_box(self))
+    }
+
+    fn as_light(&self) -> Option<SphereLight> {
+        let radiance = self.material.emitted();
+        if radiance.0 == [0.0, 0.0, 0.0] {
+            return None
+        }
 
-        Some((t, normal))
+        Some(SphereLight { center: self.center, radius: self.radius, radiance })
     }
 }
 